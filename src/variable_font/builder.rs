@@ -0,0 +1,339 @@
+//! Shelf-packing atlas builder.
+//!
+//! This `std`-gated builder packs individually-sized glyph bitmaps into a single
+//! [`MonoFont`]-compatible image together with the [`LookupTableGlyphWidthMapping`] that indexes
+//! it, so a proportional font can be authored without hand-laying-out a strip.
+//!
+//! A [`MonoFont`] locates glyph *i* on a fixed grid — cell origin
+//! `((i % cols) * cell_width, (i / cols) * cell_height)` — and
+//! [`LookupTableGlyphWidthMapping`] always draws from column 0 of that cell. The packer therefore
+//! uses the shelf-packing algorithm from pathfinder's atlas builder specialised to a fixed cell:
+//! the cell height is the tallest glyph and the cell width is the widest glyph, glyphs are placed
+//! left-to-right across a shelf until the next cell would exceed the target image width, and a new
+//! shelf (grid row) is opened below. Each glyph is left-aligned in its cell (so its start column
+//! is 0) and bottom-aligned to the shelf (so every cell shares one baseline).
+//!
+//! [`MonoFont`]: ../../mono_font/struct.MonoFont.html
+//! [`LookupTableGlyphWidthMapping`]: ../mapping/struct.LookupTableGlyphWidthMapping.html
+
+use core::fmt;
+
+use crate::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+    variable_font::mapping::LookupTableGlyphWidthMapping,
+};
+
+/// A source glyph bitmap to be packed into an atlas.
+///
+/// The pixel data is 1-bpp and MSB-first, with one bit per pixel and each row padded to the next
+/// byte boundary, matching the packing the [`MonoFont`] image uses.
+///
+/// [`MonoFont`]: ../../mono_font/struct.MonoFont.html
+#[derive(Debug, Clone)]
+pub struct GlyphBitmap {
+    /// Width of the glyph in pixels.
+    pub width: u32,
+    /// Height of the glyph in pixels.
+    pub height: u32,
+    /// 1-bpp, MSB-first pixel data, rows padded to the next byte boundary.
+    pub data: Vec<u8>,
+}
+
+/// The packed atlas produced by [`pack`].
+///
+/// Glyphs are indexed in the order they were supplied to [`pack`]; `widths` and `placements` are
+/// keyed on that same index. Build a [`MonoFont`] from `data`/`size` using `cell_size` as its
+/// `character_size`, and index it with the mapping returned by [`glyph_width_mapping`].
+///
+/// [`MonoFont`]: ../../mono_font/struct.MonoFont.html
+/// [`glyph_width_mapping`]: PackedAtlas::glyph_width_mapping
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedAtlas {
+    /// Dimensions of the packed atlas image.
+    pub size: Size,
+    /// Fixed grid cell size, to be used as the [`MonoFont`]'s `character_size`.
+    ///
+    /// [`MonoFont`]: ../../mono_font/struct.MonoFont.html
+    pub cell_size: Size,
+    /// 1-bpp, MSB-first packed image data, rows padded to the next byte boundary.
+    pub data: Vec<u8>,
+    /// Drawn width of each glyph, suitable as a [`LookupTableGlyphWidthMapping`] lookup table.
+    pub widths: Vec<u8>,
+    /// Placement rectangle of each glyph's pixels within the atlas image.
+    pub placements: Vec<Rectangle>,
+}
+
+impl PackedAtlas {
+    /// Returns a [`LookupTableGlyphWidthMapping`] borrowing this atlas's width table.
+    pub fn glyph_width_mapping(&self) -> LookupTableGlyphWidthMapping<'_> {
+        LookupTableGlyphWidthMapping::new(&self.widths, None)
+    }
+}
+
+/// An error returned by [`pack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasBuilderError {
+    /// A glyph was wider than the target atlas width and could not be placed in a cell.
+    GlyphTooWide {
+        /// Index of the offending glyph.
+        index: usize,
+        /// Width of the glyph.
+        width: u32,
+        /// Target atlas width.
+        atlas_width: u32,
+    },
+    /// A glyph was wider than a [`LookupTableGlyphWidthMapping`] entry can represent (`u8::MAX`).
+    GlyphWidthNotRepresentable {
+        /// Index of the offending glyph.
+        index: usize,
+        /// Width of the glyph.
+        width: u32,
+    },
+    /// A glyph's pixel data was shorter than its declared dimensions require.
+    GlyphDataTooShort {
+        /// Index of the offending glyph.
+        index: usize,
+        /// Number of bytes supplied.
+        len: usize,
+        /// Number of bytes required for the glyph's width and height.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for AtlasBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GlyphTooWide {
+                index,
+                width,
+                atlas_width,
+            } => write!(
+                f,
+                "glyph {} is {} pixels wide, exceeding the atlas width of {}",
+                index, width, atlas_width
+            ),
+            Self::GlyphWidthNotRepresentable { index, width } => write!(
+                f,
+                "glyph {} is {} pixels wide, exceeding the maximum lookup-table width of {}",
+                index,
+                width,
+                u8::MAX
+            ),
+            Self::GlyphDataTooShort {
+                index,
+                len,
+                expected,
+            } => write!(
+                f,
+                "glyph {} supplied {} bytes of data but requires {}",
+                index, len, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AtlasBuilderError {}
+
+/// Packs `glyphs` into a single fixed-grid atlas at most `atlas_width` pixels wide.
+///
+/// The returned [`PackedAtlas`] holds the packed image, its grid cell size, a width lookup table
+/// and the per-glyph placement rectangles; see the [module documentation] for the packing
+/// algorithm.
+///
+/// # Errors
+///
+/// - [`AtlasBuilderError::GlyphTooWide`] if any glyph is wider than `atlas_width` (no cell fits).
+/// - [`AtlasBuilderError::GlyphWidthNotRepresentable`] if any glyph is wider than `u8::MAX`, since
+///   the width lookup table stores widths as `u8`.
+/// - [`AtlasBuilderError::GlyphDataTooShort`] if any glyph's `data` is too short for its declared
+///   width and height.
+///
+/// [module documentation]: index.html
+pub fn pack(glyphs: &[GlyphBitmap], atlas_width: u32) -> Result<PackedAtlas, AtlasBuilderError> {
+    for (index, glyph) in glyphs.iter().enumerate() {
+        if glyph.width > atlas_width {
+            return Err(AtlasBuilderError::GlyphTooWide {
+                index,
+                width: glyph.width,
+                atlas_width,
+            });
+        }
+        if glyph.width > u32::from(u8::MAX) {
+            return Err(AtlasBuilderError::GlyphWidthNotRepresentable {
+                index,
+                width: glyph.width,
+            });
+        }
+        let expected = bytes_per_row(glyph.width) * glyph.height as usize;
+        if glyph.data.len() < expected {
+            return Err(AtlasBuilderError::GlyphDataTooShort {
+                index,
+                len: glyph.data.len(),
+                expected,
+            });
+        }
+    }
+
+    if glyphs.is_empty() {
+        return Ok(PackedAtlas {
+            size: Size::zero(),
+            cell_size: Size::zero(),
+            data: Vec::new(),
+            widths: Vec::new(),
+            placements: Vec::new(),
+        });
+    }
+
+    // A fixed grid cell sized to the widest and tallest glyph, so that every glyph's origin is
+    // grid-computable by a MonoFont and every cell shares one baseline.
+    let cell_width = glyphs.iter().map(|glyph| glyph.width).max().unwrap_or(0);
+    let cell_height = glyphs.iter().map(|glyph| glyph.height).max().unwrap_or(0);
+
+    // Number of cells per shelf; at least one since `cell_width <= atlas_width` after validation.
+    let cols = (atlas_width / cell_width.max(1)).max(1) as usize;
+    let rows = (glyphs.len() + cols - 1) / cols;
+
+    let image_width = cols as u32 * cell_width;
+    let image_height = rows as u32 * cell_height;
+    let size = Size::new(image_width, image_height);
+    let cell_size = Size::new(cell_width, cell_height);
+
+    let dst_stride = bytes_per_row(image_width);
+    let mut data = vec![0u8; dst_stride * image_height as usize];
+    let mut widths = Vec::with_capacity(glyphs.len());
+    let mut placements = Vec::with_capacity(glyphs.len());
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let col = (index % cols) as u32;
+        let row = (index / cols) as u32;
+        // Left-aligned in the cell (start column 0), bottom-aligned to the shelf baseline.
+        let origin = Point::new(
+            (col * cell_width) as i32,
+            (row * cell_height + (cell_height - glyph.height)) as i32,
+        );
+
+        placements.push(Rectangle::new(origin, Size::new(glyph.width, glyph.height)));
+        widths.push(glyph.width as u8);
+        blit(glyph, origin, &mut data, dst_stride);
+    }
+
+    Ok(PackedAtlas {
+        size,
+        cell_size,
+        data,
+        widths,
+        placements,
+    })
+}
+
+/// Number of bytes used to store one row of a 1-bpp image `width` pixels wide.
+fn bytes_per_row(width: u32) -> usize {
+    ((width + 7) / 8) as usize
+}
+
+/// Copies a glyph's 1-bpp pixels into the atlas buffer at `origin`.
+fn blit(glyph: &GlyphBitmap, origin: Point, data: &mut [u8], dst_stride: usize) {
+    let src_stride = bytes_per_row(glyph.width);
+    for y in 0..glyph.height {
+        for x in 0..glyph.width {
+            let src_bit =
+                (glyph.data[y as usize * src_stride + (x / 8) as usize] >> (7 - x % 8)) & 1;
+            if src_bit == 0 {
+                continue;
+            }
+
+            let px = origin.x as u32 + x;
+            let py = origin.y as u32 + y;
+            data[py as usize * dst_stride + (px / 8) as usize] |= 1 << (7 - px % 8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(width: u32, height: u32, data: &[u8]) -> GlyphBitmap {
+        GlyphBitmap {
+            width,
+            height,
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn packs_onto_a_fixed_grid() {
+        // Glyph A: 3x2, rows "111" / "101". Glyph B: 2x1, row "11".
+        let glyphs = [glyph(3, 2, &[0xE0, 0xA0]), glyph(2, 1, &[0xC0])];
+
+        let atlas = pack(&glyphs, 8).unwrap();
+
+        // cols = 8 / 3 = 2; one grid row; cell 3x2; image 6x2.
+        assert_eq!(atlas.cell_size, Size::new(3, 2));
+        assert_eq!(atlas.size, Size::new(6, 2));
+        assert_eq!(atlas.widths, [3, 2]);
+        assert_eq!(
+            atlas.placements,
+            [
+                Rectangle::new(Point::new(0, 0), Size::new(3, 2)),
+                // B is bottom-aligned in the 2-row cell, so it sits on the second row.
+                Rectangle::new(Point::new(3, 1), Size::new(2, 1)),
+            ]
+        );
+        assert_eq!(atlas.data, [0xE0, 0xB8]);
+    }
+
+    #[test]
+    fn empty_input_produces_an_empty_atlas() {
+        let atlas = pack(&[], 16).unwrap();
+        assert_eq!(atlas.size, Size::zero());
+        assert!(atlas.data.is_empty());
+        assert!(atlas.placements.is_empty());
+    }
+
+    #[test]
+    fn bytes_per_row_rounds_up() {
+        assert_eq!(bytes_per_row(0), 0);
+        assert_eq!(bytes_per_row(1), 1);
+        assert_eq!(bytes_per_row(8), 1);
+        assert_eq!(bytes_per_row(9), 2);
+    }
+
+    #[test]
+    fn rejects_glyph_wider_than_atlas() {
+        let glyphs = [glyph(10, 1, &[0x00, 0x00])];
+        assert_eq!(
+            pack(&glyphs, 5),
+            Err(AtlasBuilderError::GlyphTooWide {
+                index: 0,
+                width: 10,
+                atlas_width: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_glyph_wider_than_lookup_entry() {
+        let glyphs = [glyph(300, 1, &[])];
+        assert_eq!(
+            pack(&glyphs, 400),
+            Err(AtlasBuilderError::GlyphWidthNotRepresentable {
+                index: 0,
+                width: 300,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_glyph_with_short_data() {
+        let glyphs = [glyph(8, 2, &[0x00])];
+        assert_eq!(
+            pack(&glyphs, 8),
+            Err(AtlasBuilderError::GlyphDataTooShort {
+                index: 0,
+                len: 1,
+                expected: 2,
+            })
+        );
+    }
+}
@@ -8,6 +8,60 @@
 use crate::mono_font::MonoFont;
 use core::ops::Range;
 
+/// Full horizontal metrics for a single glyph.
+///
+/// Modeled on the standard typographic horizontal metrics (as used by the Trezor glyph struct):
+/// the glyph is shifted by `bearing_x` from the current pen position, the columns `x..x + width`
+/// of the [`MonoFont`] glyph image are painted, and the pen is advanced by `advance` afterwards.
+///
+/// `advance` is independent of `width`: it may be larger (to reserve whitespace that isn't
+/// painted) or smaller (to let adjacent glyphs overlap slightly), and `bearing_x` may be negative
+/// to position the glyph to the left of the pen.
+///
+/// [`VariableFont::glyph`] only crops the drawn `x..x + width` columns; `bearing_x` and `advance`
+/// are consumed by the text renderer via [`VariableFont::glyphs`], which advances the pen by
+/// `advance` and yields `bearing_x` alongside each glyph's (unshifted) pen position.
+///
+/// [`VariableFont::glyph`]: super::VariableFont::glyph
+/// [`VariableFont::glyphs`]: super::VariableFont::glyphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphMetrics {
+    /// First column of the [`MonoFont`] glyph image to draw.
+    pub x: u32,
+    /// Number of columns of the [`MonoFont`] glyph image to draw.
+    pub width: u32,
+    /// Left-side bearing applied to the pen position before drawing.
+    pub bearing_x: i32,
+    /// Advance width applied to the pen position after drawing.
+    pub advance: u32,
+}
+
+/// Mapping from characters to full horizontal glyph metrics.
+///
+/// Every [`GlyphWidthMapping`] is also a `GlyphMetricsMapping` via a blanket implementation which
+/// draws the whole returned range (`bearing_x = 0`, `advance = width`); implement this trait
+/// directly to return left-side bearings or advance widths which differ from the drawn width.
+pub trait GlyphMetricsMapping {
+    /// Maps a character to its full horizontal glyph metrics.
+    fn glyph_metrics(&self, font: &MonoFont<'_>, c: char) -> GlyphMetrics;
+}
+
+impl<T> GlyphMetricsMapping for T
+where
+    T: GlyphWidthMapping,
+{
+    fn glyph_metrics(&self, font: &MonoFont<'_>, c: char) -> GlyphMetrics {
+        let width = self.glyph_width(font, c);
+        let range_size = width.range_size();
+        GlyphMetrics {
+            x: if width.is_empty() { 0 } else { width.start },
+            width: range_size,
+            bearing_x: 0,
+            advance: range_size,
+        }
+    }
+}
+
 /// Mapping from characters to glyph widths.
 pub trait GlyphWidthMapping {
     /// Maps a character to a glyph width.
@@ -59,6 +113,215 @@ impl GlyphWidthMapping for LookupTableGlyphWidthMapping<'_> {
     }
 }
 
+/// Vertical metrics for a single glyph.
+///
+/// The vertical counterpart of [`GlyphMetrics`]: the rows `y..y + height` of the [`MonoFont`]
+/// glyph image are painted, cropping empty rows above and below, and the glyph is shifted by
+/// `bearing_y` from the baseline before drawing (positive values raise the glyph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerticalGlyphMetrics {
+    /// First row of the [`MonoFont`] glyph image to draw.
+    pub y: u32,
+    /// Number of rows of the [`MonoFont`] glyph image to draw.
+    pub height: u32,
+    /// Signed vertical bearing applied to the pen position before drawing.
+    pub bearing_y: i32,
+}
+
+/// Mapping from characters to vertical glyph metrics.
+///
+/// This lets a glyph crop its empty top/bottom rows and sit above or below the shared baseline,
+/// enabling compact line heights, superscripts and subscripts from a single [`MonoFont`] strip.
+pub trait VerticalGlyphMapping {
+    /// Maps a character to its vertical glyph metrics.
+    fn vertical_glyph_metrics(&self, font: &MonoFont<'_>, c: char) -> VerticalGlyphMetrics;
+
+    /// Total number of rows spanned by the union of all glyph extents.
+    ///
+    /// Defaults to the font's own character height; override it when glyphs are raised or lowered
+    /// beyond the strip so that surrounding layout still reserves the correct vertical space.
+    fn character_height(&self, font: &MonoFont<'_>) -> u32 {
+        font.character_size.height
+    }
+
+    /// Baseline position within the unioned [`character_height`](Self::character_height).
+    ///
+    /// Defaults to the font's own baseline.
+    fn baseline(&self, font: &MonoFont<'_>) -> u32 {
+        font.baseline
+    }
+}
+
+impl<F> VerticalGlyphMapping for F
+where
+    F: Fn(&MonoFont<'_>, char) -> VerticalGlyphMetrics,
+{
+    fn vertical_glyph_metrics(&self, font: &MonoFont<'_>, c: char) -> VerticalGlyphMetrics {
+        self(font, c)
+    }
+}
+
+/// A lookup-table-based vertical glyph mapping.
+///
+/// The table is indexed by the glyph index returned from the [`MonoFont`]'s glyph mapping; entries
+/// beyond the table fall back to `default_metrics`, or to the full, unshifted glyph extent if none
+/// is given.
+///
+/// Unlike the closure-based mappings, this type overrides
+/// [`character_height`](VerticalGlyphMapping::character_height) and
+/// [`baseline`](VerticalGlyphMapping::baseline) to report the union of every table entry's extent,
+/// so raised or lowered glyphs reserve the correct vertical space instead of clipping.
+#[derive(Debug, Clone)]
+pub struct LookupTableVerticalGlyphMapping<'a> {
+    lookup_table: &'a [VerticalGlyphMetrics],
+    default_metrics: Option<VerticalGlyphMetrics>,
+}
+
+impl<'a> LookupTableVerticalGlyphMapping<'a> {
+    /// Creates a new lookup-table-based vertical glyph mapping.
+    pub const fn new(
+        lookup_table: &'a [VerticalGlyphMetrics],
+        default_metrics: Option<VerticalGlyphMetrics>,
+    ) -> Self {
+        Self {
+            lookup_table,
+            default_metrics,
+        }
+    }
+
+    /// Vertical span `[top, bottom)` of a single entry in line-box coordinates, where the glyph is
+    /// raised above the cell top by its `bearing_y`.
+    fn extent(metrics: &VerticalGlyphMetrics) -> (i32, i32) {
+        let top = metrics.y as i32 - metrics.bearing_y;
+        (top, top + metrics.height as i32)
+    }
+
+    /// Metrics returned for characters not present in the lookup table: `default_metrics` if set,
+    /// otherwise the full, unshifted font cell.
+    fn fallback_metrics(&self, font: &MonoFont<'_>) -> VerticalGlyphMetrics {
+        self.default_metrics.unwrap_or(VerticalGlyphMetrics {
+            y: 0,
+            height: font.character_size.height,
+            bearing_y: 0,
+        })
+    }
+
+    /// Unioned `[min_top, max_bottom)` span of every table entry and the fallback metrics, in
+    /// line-box coordinates.
+    ///
+    /// The span is seeded from [`fallback_metrics`](Self::fallback_metrics) — the extent used for
+    /// out-of-table characters — rather than the full font cell, so a table of cropped glyphs with
+    /// a matching (compact) default reports a line box shorter than the original cell.
+    fn union(&self, font: &MonoFont<'_>) -> (i32, i32) {
+        let (mut min_top, mut max_bottom) = Self::extent(&self.fallback_metrics(font));
+        for metrics in self.lookup_table {
+            let (top, bottom) = Self::extent(metrics);
+            min_top = min_top.min(top);
+            max_bottom = max_bottom.max(bottom);
+        }
+        (min_top, max_bottom)
+    }
+}
+
+impl VerticalGlyphMapping for LookupTableVerticalGlyphMapping<'_> {
+    fn vertical_glyph_metrics(&self, font: &MonoFont<'_>, c: char) -> VerticalGlyphMetrics {
+        let glyph_index = font.glyph_mapping.index(c);
+        if glyph_index < self.lookup_table.len() {
+            self.lookup_table[glyph_index]
+        } else {
+            self.fallback_metrics(font)
+        }
+    }
+
+    fn character_height(&self, font: &MonoFont<'_>) -> u32 {
+        let (min_top, max_bottom) = self.union(font);
+        (max_bottom - min_top).max(0) as u32
+    }
+
+    fn baseline(&self, font: &MonoFont<'_>) -> u32 {
+        let (min_top, max_bottom) = self.union(font);
+        // Keep the baseline within the (possibly compacted) line box; a box that ends above the
+        // font baseline has no descender space, so the baseline sits on its bottom edge.
+        (font.baseline as i32 - min_top).clamp(0, max_bottom - min_top) as u32
+    }
+}
+
+/// A single kerning-table entry.
+///
+/// Applies a signed horizontal adjustment between the glyphs with indices `left` and `right`
+/// (as returned by the [`MonoFont`]'s glyph mapping).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KerningPair {
+    /// Glyph index of the left-hand character.
+    pub left: usize,
+    /// Glyph index of the right-hand character.
+    pub right: usize,
+    /// Signed adjustment added to the pen position between the two glyphs.
+    pub kerning: i32,
+}
+
+/// Mapping from glyph pairs to a signed horizontal kerning adjustment.
+pub trait KerningMapping {
+    /// Returns the kerning adjustment applied between `left` and `right`.
+    fn kerning(&self, font: &MonoFont<'_>, left: char, right: char) -> i32;
+}
+
+impl<F> KerningMapping for F
+where
+    F: Fn(&MonoFont<'_>, char, char) -> i32,
+{
+    fn kerning(&self, font: &MonoFont<'_>, left: char, right: char) -> i32 {
+        self(font, left, right)
+    }
+}
+
+/// A lookup-table-based kerning mapping keyed on glyph-index pairs.
+///
+/// The table is searched for the entry whose `left`/`right` glyph indices match the pair being
+/// measured; pairs with no entry are not kerned (zero adjustment).
+#[derive(Debug, Clone)]
+pub struct LookupTableKerningMapping<'a> {
+    lookup_table: &'a [KerningPair],
+}
+
+impl<'a> LookupTableKerningMapping<'a> {
+    /// Creates a new lookup-table-based kerning mapping.
+    pub const fn new(lookup_table: &'a [KerningPair]) -> Self {
+        Self { lookup_table }
+    }
+}
+
+impl KerningMapping for LookupTableKerningMapping<'_> {
+    fn kerning(&self, font: &MonoFont<'_>, left: char, right: char) -> i32 {
+        let left = font.glyph_mapping.index(left);
+        let right = font.glyph_mapping.index(right);
+        self.lookup_table
+            .iter()
+            .find(|pair| pair.left == left && pair.right == right)
+            .map_or(0, |pair| pair.kerning)
+    }
+}
+
+/// Mapping from a run of input characters to a single substitute glyph.
+///
+/// This allows a sequence such as `"fi"` or `"->"` to be drawn as one wider glyph from the
+/// underlying strip. Implementations are consulted at every cursor position and must return the
+/// *longest* applicable match, so that measurement and drawing perform the same greedy iteration.
+pub trait LigatureMapping {
+    /// Given the remaining text at the cursor, optionally returns the number of characters to
+    /// consume and the substitute glyph to draw in their place.
+    fn ligature(&self, remaining: &str) -> Option<(usize, char)>;
+}
+
+impl<F> LigatureMapping for F
+where
+    F: Fn(&str) -> Option<(usize, char)>,
+{
+    fn ligature(&self, remaining: &str) -> Option<(usize, char)> {
+        self(remaining)
+    }
+}
+
 pub(crate) trait RangeSize {
     fn range_size(&self) -> u32;
 }
@@ -9,17 +9,20 @@
 //! [`MonoFont`]: ../mono_font/struct.MonoFont.html
 //! [`GlyphWidthMapping`]: trait.GlyphWidthMapping.html
 
+#[cfg(feature = "std")]
+pub mod builder;
 pub mod mapping;
 
 use core::{convert::TryInto, fmt};
 
 use crate::{
-    geometry::{OriginDimensions, Point, Size},
+    geometry::{Point, Size},
     mono_font::{
         DecorationDimensions, Font, MonoFont,
     },
-    variable_font::{
-        mapping::{GlyphWidthMapping, RangeSize},
+    variable_font::mapping::{
+        GlyphMetricsMapping, KerningMapping, LigatureMapping, VerticalGlyphMapping,
+        VerticalGlyphMetrics,
     },
     primitives::Rectangle,
 };
@@ -34,30 +37,192 @@ pub struct VariableFont<'a> {
     /// The underlying monospaced font.
     pub mono_font: MonoFont<'a>,
 
-    /// Glyph width mapping.
-    pub glyph_width_mapping: &'a dyn GlyphWidthMapping,
+    /// Glyph metrics mapping.
+    ///
+    /// Any [`GlyphWidthMapping`] may be used here thanks to its blanket [`GlyphMetricsMapping`]
+    /// implementation.
+    ///
+    /// [`GlyphWidthMapping`]: mapping::GlyphWidthMapping
+    /// [`GlyphMetricsMapping`]: mapping::GlyphMetricsMapping
+    pub glyph_width_mapping: &'a dyn GlyphMetricsMapping,
+
+    /// Optional glyph-pair kerning mapping.
+    ///
+    /// When present, the signed kerning between each pair of consecutive characters is added to
+    /// the inter-glyph spacing while measuring and drawing a run.
+    pub kerning_mapping: Option<&'a dyn KerningMapping>,
+
+    /// Optional ligature mapping.
+    ///
+    /// When present, runs of input characters may be substituted by a single wider glyph while
+    /// measuring and drawing a string.
+    pub ligature_mapping: Option<&'a dyn LigatureMapping>,
+
+    /// Optional vertical glyph mapping.
+    ///
+    /// When present, each glyph crops its empty top/bottom rows and is raised or lowered by its
+    /// vertical bearing; [`character_height`](Font::character_height) and
+    /// [`baseline`](Font::baseline) report the unioned glyph extents.
+    pub vertical_mapping: Option<&'a dyn VerticalGlyphMapping>,
+}
+
+impl<'a> VariableFont<'a> {
+    /// Returns the kerning adjustment between two consecutive characters.
+    ///
+    /// Evaluates to `0` when no [`KerningMapping`] is configured.
+    pub fn kerning(&self, left: char, right: char) -> i32 {
+        self.kerning_mapping
+            .map_or(0, |mapping| mapping.kerning(&self.mono_font, left, right))
+    }
+
+    /// Returns the vertical metrics of a character's glyph.
+    ///
+    /// Evaluates to the full, unshifted glyph extent when no [`VerticalGlyphMapping`] is
+    /// configured.
+    pub fn vertical_metrics(&self, c: char) -> VerticalGlyphMetrics {
+        self.vertical_mapping.map_or(
+            VerticalGlyphMetrics {
+                y: 0,
+                height: self.mono_font.character_height(),
+                bearing_y: 0,
+            },
+            |mapping| mapping.vertical_glyph_metrics(&self.mono_font, c),
+        )
+    }
+
+    /// Iterates over the positioned glyphs of a string.
+    ///
+    /// Drawing and measurement share this iterator so they stay in sync: it applies ligature
+    /// substitution, inter-glyph spacing and kerning exactly once, yielding each glyph together
+    /// with the pen position (relative to the start of the run) at which it should be drawn. A
+    /// renderer should draw each glyph at `position` shifted by the glyph's own `bearing_x`.
+    pub fn glyphs<'s>(&'s self, text: &'s str) -> Glyphs<'s> {
+        Glyphs {
+            font: self,
+            rest: text,
+            pen: 0,
+            prev: None,
+        }
+    }
+}
+
+/// A single glyph positioned within a run.
+///
+/// Produced by [`Glyphs`]. `position` is the pen x relative to the start of the run; a renderer
+/// draws the glyph at `position + bearing_x` and the vertical metrics from
+/// [`VariableFont::vertical_metrics`]. Every horizontal value the request's draw path needs is
+/// carried here, so a renderer need not re-query [`GlyphMetricsMapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionedGlyph {
+    /// Character whose glyph should be drawn.
+    pub character: char,
+    /// Pen x position of the glyph relative to the start of the run.
+    pub position: i32,
+    /// Left-side bearing applied to `position` before drawing.
+    pub bearing_x: i32,
+    /// Advance width applied to the pen after this glyph.
+    pub advance: u32,
+}
+
+/// Iterator over the positioned glyphs of a string.
+///
+/// Created by [`VariableFont::glyphs`]. Applies ligature substitution, then accumulates the pen
+/// position using the same spacing and kerning that `measure_string_width` does, so a renderer
+/// built on it reproduces the measured width exactly.
+#[derive(Clone)]
+pub struct Glyphs<'a> {
+    font: &'a VariableFont<'a>,
+    rest: &'a str,
+    pen: i32,
+    prev: Option<char>,
+}
+
+impl Glyphs<'_> {
+    /// Consumes and returns the next glyph character, applying ligature substitution.
+    fn next_glyph_char(&mut self) -> Option<char> {
+        let mut chars = self.rest.chars();
+        let first = chars.next()?;
+
+        if let Some(mapping) = self.font.ligature_mapping {
+            if let Some((consumed, glyph)) = mapping.ligature(self.rest) {
+                if consumed > 0 {
+                    let offset = self
+                        .rest
+                        .char_indices()
+                        .nth(consumed)
+                        .map_or(self.rest.len(), |(i, _)| i);
+                    self.rest = &self.rest[offset..];
+                    return Some(glyph);
+                }
+            }
+        }
+
+        self.rest = chars.as_str();
+        Some(first)
+    }
+}
+
+impl Iterator for Glyphs<'_> {
+    type Item = PositionedGlyph;
+
+    fn next(&mut self) -> Option<PositionedGlyph> {
+        let character = self.next_glyph_char()?;
+
+        if let Some(prev) = self.prev {
+            self.pen += self.font.mono_font.character_spacing() as i32;
+            self.pen += self.font.kerning(prev, character);
+        }
+
+        let position = self.pen;
+        let metrics = self
+            .font
+            .glyph_width_mapping
+            .glyph_metrics(&self.font.mono_font, character);
+        self.pen += metrics.advance as i32;
+        self.prev = Some(character);
+
+        Some(PositionedGlyph {
+            character,
+            position,
+            bearing_x: metrics.bearing_x,
+            advance: metrics.advance,
+        })
+    }
 }
 
 impl<'a> Font<'a> for VariableFont<'a> {
     type Glyph = <MonoFont<'a> as Font<'a>>::Glyph;
     fn glyph(&'a self, c: char) -> Self::Glyph {
+        // `glyph` only crops the drawn sub-image; the horizontal `bearing_x`/`advance` and the
+        // vertical `bearing_y` that position the glyph relative to the pen and baseline are
+        // applied by the consuming text renderer, which obtains them from [`Self::glyphs`] and
+        // [`Self::vertical_metrics`].
         let mono_glyph = self.mono_font.glyph(c);
-        let mono_glyph_size = mono_glyph.size();
-        let glyph_width = self.glyph_width_mapping.glyph_width(&self.mono_font, c);
+        let metrics = self.glyph_width_mapping.glyph_metrics(&self.mono_font, c);
+        let vertical = self.vertical_metrics(c);
 
         mono_glyph.sub_image(&Rectangle::new(
-            Point::new(glyph_width.start.try_into().unwrap(), 0),
-            Size::new(glyph_width.range_size(), mono_glyph_size.height),
+            Point::new(
+                metrics.x.try_into().unwrap(),
+                vertical.y.try_into().unwrap(),
+            ),
+            Size::new(metrics.width, vertical.height),
         ))
     }
     fn character_height(&self) -> u32 {
-        self.mono_font.character_height()
+        self.vertical_mapping.map_or_else(
+            || self.mono_font.character_height(),
+            |mapping| mapping.character_height(&self.mono_font),
+        )
     }
     fn character_spacing(&self) -> u32 {
         self.mono_font.character_spacing()
     }
     fn baseline(&self) -> u32 {
-        self.mono_font.baseline()
+        self.vertical_mapping.map_or_else(
+            || self.mono_font.baseline(),
+            |mapping| mapping.baseline(&self.mono_font),
+        )
     }
     fn strikethrough(&self) -> DecorationDimensions {
         self.mono_font.strikethrough()
@@ -66,15 +231,9 @@ impl<'a> Font<'a> for VariableFont<'a> {
         self.mono_font.underline()
     }
     fn measure_string_width(&self, text: &str) -> u32 {
-        text.chars()
-            .fold(0u32, |a: u32, c: char| {
-                a + self
-                    .glyph_width_mapping
-                    .glyph_width(&self.mono_font, c)
-                    .range_size()
-                    + self.mono_font.character_spacing()
-            })
-            .saturating_sub(self.mono_font.character_spacing())
+        self.glyphs(text).last().map_or(0, |glyph| {
+            (glyph.position + glyph.advance as i32).max(0) as u32
+        })
     }
 }
 
@@ -82,6 +241,21 @@ impl PartialEq for VariableFont<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.mono_font == other.mono_font
             && core::ptr::eq(self.glyph_width_mapping, other.glyph_width_mapping)
+            && match (self.kerning_mapping, other.kerning_mapping) {
+                (Some(a), Some(b)) => core::ptr::eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && match (self.ligature_mapping, other.ligature_mapping) {
+                (Some(a), Some(b)) => core::ptr::eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && match (self.vertical_mapping, other.vertical_mapping) {
+                (Some(a), Some(b)) => core::ptr::eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
     }
 }
 
@@ -90,6 +264,9 @@ impl fmt::Debug for VariableFont<'_> {
         f.debug_struct("VariableFont")
             .field("mono_font", &self.mono_font)
             .field("glyph_width_mapping", &"?")
+            .field("kerning_mapping", &self.kerning_mapping.map(|_| "?"))
+            .field("ligature_mapping", &self.ligature_mapping.map(|_| "?"))
+            .field("vertical_mapping", &self.vertical_mapping.map(|_| "?"))
             // MSRV 1.53.0: use `finish_non_exhaustive`
             .finish()
     }